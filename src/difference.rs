@@ -0,0 +1,101 @@
+//! Computing the minimal set of SGR codes needed to move from one
+//! [`Style`] to another, so [`crate::display`]'s rendering of a sequence of
+//! styled runs doesn't reset and re-emit every attribute between each one.
+use crate::style::Style;
+
+/// The escape-sequence-level relationship between two adjacent runs' styles.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Difference {
+    /// `second` can be reached from `first` by writing only the codes in
+    /// the contained `Style` (an additive delta) after `first`'s prefix.
+    ExtraStyles(Style),
+
+    /// `second` drops something `first` had turned on (an attribute, a
+    /// color, or an underline variant going back to none), which can't be
+    /// undone with more codes -- the run has to close with `RESET` and
+    /// reopen with `second`'s own prefix.
+    Reset,
+
+    /// The two styles render identically; nothing needs to be written.
+    Empty,
+}
+
+impl Difference {
+    /// Work out how to move from `first`'s styling to `second`'s.
+    pub fn between(first: &Style, second: &Style) -> Difference {
+        if first == second {
+            return Difference::Empty;
+        }
+
+        if first.is_plain() {
+            return Difference::ExtraStyles(*second);
+        }
+
+        // Turning an attribute or color off can't be expressed as an extra
+        // SGR code on top of `first` -- only a RESET clears it.
+        if (first.is_bold && !second.is_bold)
+            || (first.is_dimmed && !second.is_dimmed)
+            || (first.is_italic && !second.is_italic)
+            || (first.is_underline && !second.is_underline)
+            || (first.is_underline
+                && first.underline_color.is_some()
+                && second.underline_color.is_none())
+            || (first.is_blink && !second.is_blink)
+            || (first.is_reverse && !second.is_reverse)
+            || (first.is_hidden && !second.is_hidden)
+            || (first.is_strikethrough && !second.is_strikethrough)
+            || (first.foreground.is_some() && second.foreground.is_none())
+            || (first.background.is_some() && second.background.is_none())
+        {
+            return Difference::Reset;
+        }
+
+        let mut extra_styles = Style::default();
+
+        if first.is_bold != second.is_bold {
+            extra_styles.is_bold = second.is_bold;
+        }
+        if first.is_dimmed != second.is_dimmed {
+            extra_styles.is_dimmed = second.is_dimmed;
+        }
+        if first.is_italic != second.is_italic {
+            extra_styles.is_italic = second.is_italic;
+        }
+        if first.is_blink != second.is_blink {
+            extra_styles.is_blink = second.is_blink;
+        }
+        if first.is_reverse != second.is_reverse {
+            extra_styles.is_reverse = second.is_reverse;
+        }
+        if first.is_hidden != second.is_hidden {
+            extra_styles.is_hidden = second.is_hidden;
+        }
+        if first.is_strikethrough != second.is_strikethrough {
+            extra_styles.is_strikethrough = second.is_strikethrough;
+        }
+
+        // A colon-parameterized underline SGR overwrites whatever variant
+        // was active, the same way a new color code overwrites the old
+        // one -- so a variant or color change only needs re-emitting, not
+        // a full reset, as long as neither underlining itself nor the
+        // underline color is being lost (those cases were already handled
+        // above).
+        if first.is_underline != second.is_underline
+            || first.underline_style != second.underline_style
+            || first.underline_color != second.underline_color
+        {
+            extra_styles.is_underline = second.is_underline;
+            extra_styles.underline_style = second.underline_style;
+            extra_styles.underline_color = second.underline_color;
+        }
+
+        if first.foreground != second.foreground {
+            extra_styles.foreground = second.foreground;
+        }
+        if first.background != second.background {
+            extra_styles.background = second.background;
+        }
+
+        Difference::ExtraStyles(extra_styles)
+    }
+}
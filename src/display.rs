@@ -6,13 +6,46 @@ use std::borrow::Cow;
 use std::fmt;
 use std::io;
 
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (RFC 4648, padded) base64 encoding, used to embed arbitrary
+/// bytes (e.g. a clipboard payload) inside an OSC escape sequence.
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
 #[derive(Eq, PartialEq, Debug)]
 enum OSControl<'a, S: 'a + ToOwned + ?Sized>
 where
     <S as ToOwned>::Owned: fmt::Debug,
 {
     Title,
-    Link { url: Cow<'a, S> },
+    Link { url: Cow<'a, S>, id: Option<Cow<'a, S>> },
+    /// OSC 52: set the system clipboard. The payload is base64-encoded at
+    /// write time; `string` holds the raw (un-encoded) data.
+    Clipboard,
+    /// OSC 777: desktop notification, `string` holds the body.
+    Notify { title: Cow<'a, S> },
 }
 
 impl<'a, S: 'a + ToOwned + ?Sized> Clone for OSControl<'a, S>
@@ -21,8 +54,13 @@ where
 {
     fn clone(&self) -> Self {
         match self {
-            Self::Link { url: u } => Self::Link { url: u.clone() },
+            Self::Link { url: u, id: i } => Self::Link {
+                url: u.clone(),
+                id: i.clone(),
+            },
             Self::Title => Self::Title,
+            Self::Clipboard => Self::Clipboard,
+            Self::Notify { title: t } => Self::Notify { title: t.clone() },
         }
     }
 }
@@ -203,16 +241,102 @@ where
     where
         I: Into<Cow<'a, S>>,
     {
-        self.oscontrol = Some(OSControl::Link { url: url.into() });
+        self.oscontrol = Some(OSControl::Link {
+            url: url.into(),
+            id: None,
+        });
+    }
+
+    /// Cause the styled ANSI string to link to the given URL, tagged with an
+    /// explicit `id`.
+    ///
+    /// Terminals that support OSC 8 `id=` parameters will treat every run
+    /// sharing the same `id` as a single logical hyperlink for the purposes
+    /// of hover-highlighting and click handling, even if the runs are
+    /// separated by other styled text or a line wrap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nu_ansi_term::Color::Red;
+    ///
+    /// let mut link_string = Red.paint("a red string");
+    /// link_string.hyperlink_with_id("https://www.example.com", "link-1");
+    /// println!("{}", link_string);
+    /// ```
+    pub fn hyperlink_with_id<I, J>(&mut self, url: I, id: J)
+    where
+        I: Into<Cow<'a, S>>,
+        J: Into<Cow<'a, S>>,
+    {
+        self.oscontrol = Some(OSControl::Link {
+            url: url.into(),
+            id: Some(id.into()),
+        });
     }
 
     /// Get any URL associated with the string
     pub fn url_string(&self) -> Option<&S> {
         match &self.oscontrol {
-            Some(OSControl::Link { url: u }) => Some(u.as_ref()),
+            Some(OSControl::Link { url: u, .. }) => Some(u.as_ref()),
             _ => None,
         }
     }
+
+    /// Get the hyperlink `id` associated with the string, if any.
+    pub fn url_id(&self) -> Option<&S> {
+        match &self.oscontrol {
+            Some(OSControl::Link { id: Some(i), .. }) => Some(i.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Produce an ANSI string that writes `data` to the system clipboard via
+    /// OSC 52 (base64-encoded automatically).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nu_ansi_term::AnsiGenericString;
+    /// let clip = AnsiGenericString::clipboard("copied text");
+    /// println!("{}", clip);
+    /// ```
+    /// Should produce an empty line but set the system clipboard.
+    pub fn clipboard<I>(data: I) -> Self
+    where
+        I: Into<Cow<'a, S>>,
+    {
+        Self {
+            style: Style::default(),
+            string: data.into(),
+            oscontrol: Some(OSControl::<'a, S>::Clipboard),
+            wrap_zw: None,
+        }
+    }
+
+    /// Produce an ANSI string that raises a desktop notification with the
+    /// given `title` and `body` via OSC 777.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nu_ansi_term::AnsiGenericString;
+    /// let notification = AnsiGenericString::notify("Build", "Build finished");
+    /// println!("{}", notification);
+    /// ```
+    /// Should produce an empty line but raise a desktop notification.
+    pub fn notify<I, J>(title: J, body: I) -> Self
+    where
+        I: Into<Cow<'a, S>>,
+        J: Into<Cow<'a, S>>,
+    {
+        Self {
+            style: Style::default(),
+            string: body.into(),
+            oscontrol: Some(OSControl::Notify { title: title.into() }),
+            wrap_zw: None,
+        }
+    }
 }
 
 /// A set of `AnsiGenericStrings`s collected together, in order to be
@@ -308,6 +432,7 @@ impl<'a, S: 'a + ToOwned + ?Sized> AnsiGenericString<'a, S>
 where
     <S as ToOwned>::Owned: fmt::Debug,
     &'a S: AsRef<[u8]>,
+    S: AsRef<[u8]>,
 {
     // write the part within the styling prefix and suffix
     fn write_inner<W: AnyWrite<Wstr = S> + ?Sized>(
@@ -335,7 +460,7 @@ where
 
         macro_rules! OSC {
             ($code:literal) => {
-                if !*in_zw && !self.wrap_zw.is_some() {
+                if !*in_zw {
                     write!(w, "{}\x1B]{};", zwbegin, $code)?;
                     *in_zw = true;
                 } else {
@@ -357,8 +482,13 @@ where
         }
 
         match &self.oscontrol {
-            Some(OSControl::Link { url: u }) => {
-                OSC!("8;");
+            Some(OSControl::Link { url: u, id }) => {
+                OSC!("8");
+                if let Some(i) = id {
+                    write!(w, "id=")?;
+                    w.write_str(i.as_ref())?;
+                }
+                write!(w, ";")?;
                 w.write_str(u.as_ref())?;
                 if self.wrap_zw.is_some() {
                     write!(w, "\x1B\x5C{}", zwend)?;
@@ -367,7 +497,12 @@ where
                     write!(w, "\x1B\x5C")?;
                 }
                 w.write_str(self.string.as_ref())?;
-                OSC!("8;");
+                OSC!("8");
+                if let Some(i) = id {
+                    write!(w, "id=")?;
+                    w.write_str(i.as_ref())?;
+                }
+                write!(w, ";")?;
                 OSC_ST!()
             }
             Some(OSControl::Title) => {
@@ -375,6 +510,18 @@ where
                 w.write_str(self.string.as_ref())?;
                 OSC_ST!()
             }
+            Some(OSControl::Clipboard) => {
+                OSC!("52;c");
+                write!(w, "{}", base64_encode(self.string.as_ref().as_ref()))?;
+                OSC_ST!()
+            }
+            Some(OSControl::Notify { title }) => {
+                OSC!("777;notify");
+                w.write_str(title.as_ref())?;
+                write!(w, ";")?;
+                w.write_str(self.string.as_ref())?;
+                OSC_ST!()
+            }
             None => {
                 if *in_zw {
                     write!(w, "{}", zwend)?;
@@ -386,26 +533,21 @@ where
     }
 
     fn write_to_any<W: AnyWrite<Wstr = S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
-        let zwbegin: &str;
-        let zwend: &str;
-        match self.wrap_zw {
-            Some(Wrapping::CtrlACtrlB) => {
-                zwbegin = "\x01";
-                zwend = "\x02";
-            }
-            Some(Wrapping::Str(begins, ends)) => {
-                zwbegin = &begins;
-                zwend = &ends;
-            }
-            None => {
-                zwbegin = &"";
-                zwend = &"";
-            }
+        let zwbegin: &str = match self.wrap_zw {
+            Some(Wrapping::CtrlACtrlB) => "\x01",
+            Some(Wrapping::Str(begins, _)) => begins,
+            None => "",
+        };
+        let mut in_zw: bool;
+        if self.wrap_zw.is_some() {
+            write!(w, "{}{}", self.style.prefix(), zwbegin)?;
+            in_zw = true;
+        } else {
+            write!(w, "{}", self.style.prefix())?;
+            in_zw = false;
         }
-        let mut in_zw: bool = true;
-        write!(w, "{}{}", zwbegin, self.style.prefix())?;
-        self.write_inner(w, &mut in_zw, self.wrap_zw.is_some())?;
-        write!(w, "{}{}", self.style.suffix(), zwend)
+        self.write_inner(w, &mut in_zw, false)?;
+        write!(w, "{}", self.style.suffix())
     }
 }
 
@@ -428,20 +570,26 @@ impl<'a> AnsiByteStrings<'a> {
     }
 }
 
-impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> AnsiGenericStrings<'a, S>
+/// Write a sequence of `AnsiGenericString`s to `w` with a minimum of control
+/// characters, sharing the escape-minimizing logic used by both the
+/// borrowed [`AnsiGenericStrings`] and the owned [`AnsiGenericStringVec`].
+fn write_ansi_strings_to_any<'a, S: 'a + ToOwned + ?Sized + PartialEq, W: AnyWrite<Wstr = S> + ?Sized>(
+    strings: &[AnsiGenericString<'a, S>],
+    w: &mut W,
+) -> Result<(), W::Error>
 where
     <S as ToOwned>::Owned: fmt::Debug,
     &'a S: AsRef<[u8]>,
+    S: AsRef<[u8]>,
 {
-    fn write_to_any<W: AnyWrite<Wstr = S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
-        use self::Difference::*;
-        let mut zwbegin: &str;
-        let mut zwend: &str;
+    use self::Difference::*;
+    let mut zwbegin: &str;
+    let mut zwend: &str;
 
-        let first = match self.0.first() {
-            None => return Ok(()),
-            Some(f) => f,
-        };
+    let first = match strings.first() {
+        None => return Ok(()),
+        Some(f) => f,
+    };
 
         match first.wrap_zw {
             Some(Wrapping::CtrlACtrlB) => {
@@ -461,7 +609,7 @@ where
 
         let mut in_zw = false; // in zero-width and wrap_zw was set
         let mut wrap_zw_continues = first.wrap_zw.is_some()
-            && match self.0.get(1) {
+            && match strings.get(1) {
                 None => false,
                 Some(second) => second.wrap_zw.is_some(),
             };
@@ -474,7 +622,7 @@ where
         }
         first.write_inner(w, &mut in_zw, wrap_zw_continues)?;
 
-        for window in self.0.windows(2) {
+        for window in strings.windows(2) {
             wrap_zw_continues = window[0].wrap_zw.is_some() && window[1].wrap_zw.is_some();
             styling |= !window[1].style.is_plain();
             match window[1].wrap_zw {
@@ -513,21 +661,135 @@ where
         // Write the final reset string after all of the AnsiStrings have been
         // written, *except* if the last one has no styles, because it would
         // have already been written by this point.
-        if let Some(last) = self.0.last() {
+        if let Some(last) = strings.last() {
             if styling || !last.style.is_plain() {
                 if in_zw {
                     write!(w, "{}{}", RESET, zwend)?;
                 } else {
-                    if last.wrap_zw.is_some() {
-                        write!(w, "{}{}{}", zwbegin, RESET, zwend)?;
-                    } else {
-                        write!(w, "{}", RESET)?;
-                    }
+                    write!(w, "{}", RESET)?;
                 }
              }
         }
 
         Ok(())
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> AnsiGenericStrings<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    &'a S: AsRef<[u8]>,
+    S: AsRef<[u8]>,
+{
+    fn write_to_any<W: AnyWrite<Wstr = S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        write_ansi_strings_to_any(self.0, w)
+    }
+}
+
+/// An owned, growable counterpart to [`AnsiGenericStrings`].
+///
+/// `AnsiGenericStrings` only borrows a slice, so a function that assembles
+/// styled output incrementally has nowhere to put the backing `Vec` other
+/// than the caller's stack frame. `AnsiGenericStringVec` owns its runs
+/// instead, so it can be built up with `push`/`extend` and returned, while
+/// still sharing the same minimal-control-character `Display`/`write_to`
+/// logic as the borrowed form.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AnsiGenericStringVec<'a, S: 'a + ToOwned + ?Sized>(Vec<AnsiGenericString<'a, S>>)
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq;
+
+// As with the hand-written `Clone` impl above, `#[derive(Default)]` would
+// add a spurious `S: Default` bound that an unsized `S` (`str`, `[u8]`) can
+// never satisfy, so it's implemented by hand here instead.
+impl<'a, S: 'a + ToOwned + ?Sized> Default for AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+/// An owned, growable counterpart to [`AnsiStrings`].
+pub type AnsiStringVec<'a> = AnsiGenericStringVec<'a, str>;
+
+/// An owned, growable counterpart to [`AnsiByteStrings`].
+pub type AnsiByteStringVec<'a> = AnsiGenericStringVec<'a, [u8]>;
+
+impl<'a, S: 'a + ToOwned + ?Sized> AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    /// Create a new, empty collection.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Append an `AnsiGenericString` to the end of the collection.
+    pub fn push(&mut self, s: AnsiGenericString<'a, S>) {
+        self.0.push(s);
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized> std::ops::Deref for AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    type Target = [AnsiGenericString<'a, S>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized> std::ops::DerefMut for AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized> Extend<AnsiGenericString<'a, S>> for AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    fn extend<I: IntoIterator<Item = AnsiGenericString<'a, S>>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized> FromIterator<AnsiGenericString<'a, S>> for AnsiGenericStringVec<'a, S>
+where
+    <S as ToOwned>::Owned: fmt::Debug,
+    S: PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = AnsiGenericString<'a, S>>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
+
+impl<'a> fmt::Display for AnsiStringVec<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let f: &mut dyn fmt::Write = f;
+        write_ansi_strings_to_any(&self.0, f)
+    }
+}
+
+impl<'a> AnsiByteStringVec<'a> {
+    /// Write `AnsiByteStringVec` to an `io::Write`.  This writes the minimal
+    /// escape sequences for the associated `Style`s around each set of
+    /// bytes.
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let w: &mut dyn io::Write = w;
+        write_ansi_strings_to_any(&self.0, w)
     }
 }
 
@@ -535,7 +797,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    pub use super::super::{AnsiGenericString, AnsiStrings, Wrapping};
+    pub use super::super::{AnsiGenericString, AnsiStringVec, AnsiStrings, Wrapping};
     pub use crate::style::Color::*;
     pub use crate::style::Style;
 
@@ -547,6 +809,18 @@ mod tests {
         assert_eq!(output, "onetwo");
     }
 
+    #[test]
+    fn owned_vec_matches_borrowed_slice() {
+        let mut built = AnsiStringVec::new();
+        built.push(Green.paint("Hello, "));
+        built.extend([Style::default().paint("world")]);
+        assert_eq!(built.len(), 2);
+
+        let owned = built.to_string();
+        let borrowed = AnsiStrings(&built).to_string();
+        assert_eq!(owned, borrowed);
+    }
+
     // NOTE: unstyled because it could have OSC escape sequences
     fn idempotent(unstyled: AnsiGenericString<'_, str>) {
         let before_g = Green.paint("Before is Green. ");
@@ -623,6 +897,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hyperlink_with_id() {
+        let mut styled = Red.paint("Link to example.com.");
+        styled.wrap_zw = Some(Wrapping::CtrlACtrlB);
+        styled.hyperlink_with_id("https://example.com", "link-1");
+        assert_eq!(styled.url_id(), Some("link-1"));
+        assert_eq!(
+            styled.to_string(),
+            "\x1B[31m\x01\x1B]8;id=link-1;https://example.com\x1B\\\x02Link to example.com.\x01\x1B]8;id=link-1;\x1B\\\x02\x1B[0m"
+        );
+    }
+
+    #[test]
+    fn clipboard() {
+        let clip = AnsiGenericString::clipboard("hi");
+        assert_eq!(clip.to_string(), "\x1B]52;c;aGk=\x1B\\");
+    }
+
+    #[test]
+    fn notify() {
+        let notification = AnsiGenericString::notify("Build", "Build finished");
+        assert_eq!(
+            notification.to_string(),
+            "\x1B]777;notify;Build;Build finished\x1B\\"
+        );
+    }
+
     #[test]
     fn hyperlinks() {
         let before = Green.paint("Before link. ");
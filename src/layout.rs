@@ -0,0 +1,262 @@
+//! Width-aware line wrapping for [`AnsiString`] runs.
+//!
+//! This implements Oppen's two-pass pretty-printing algorithm: a *scan*
+//! pass walks the token stream once to work out how wide each group and
+//! break is, and a *print* pass then walks it again, armed with those
+//! sizes, to decide where to actually insert line breaks.
+//!
+//! The token stream is the usual `String`/`Break`/`Begin`/`End` vocabulary:
+//! a `Begin`/`End` pair delimits a group, and a `Break` inside a group is a
+//! point a line may be split at. A `Consistent` group breaks at every
+//! `Break` it contains as soon as any one of them doesn't fit; an
+//! `Inconsistent` group ("fill") breaks only at the individual `Break`s
+//! that don't fit, packing as much onto each line as possible.
+//!
+//! Width is measured purely from the visible text carried by `String`
+//! tokens. An [`AnsiString`]'s content never includes the SGR/OSC escape
+//! bytes that dress it up for a terminal -- those live in its `Style` and
+//! are only materialized by `Display` -- so measuring `as_str().chars().count()`
+//! already ignores escapes and `wrap_zw` framing for free.
+use crate::display::{AnsiString, AnsiStringVec};
+use crate::style::Style;
+use std::borrow::Cow;
+
+/// An oversized placeholder used while a group or break's true size is
+/// still being discovered by the scan pass; anything this large always
+/// counts as "doesn't fit".
+const INFINITY: isize = isize::MAX / 2;
+
+/// How the breaks inside a [`Token::Begin`] group relate to one another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breaks {
+    /// Break at every `Break` in the group as soon as the group as a
+    /// whole doesn't fit on the current line.
+    Consistent,
+    /// Break only at the individual `Break`s that don't fit ("fill").
+    Inconsistent,
+}
+
+/// A point at which a line may be split.
+#[derive(Debug, Clone, Copy)]
+pub struct BreakToken {
+    /// Width of the separator emitted when this break does *not* fire.
+    pub blank_space: usize,
+    /// Extra indent applied to the new line when this break *does* fire.
+    pub indent: isize,
+}
+
+/// The start of a group of tokens that break together.
+#[derive(Debug, Clone, Copy)]
+pub struct BeginToken {
+    /// Indent added to the enclosing group's indent for this group.
+    pub offset: isize,
+    pub kind: Breaks,
+}
+
+/// One element of the token stream fed to [`print`].
+#[derive(Debug, Clone)]
+pub enum Token<'a> {
+    String(AnsiString<'a>),
+    Break(BreakToken),
+    Begin(BeginToken),
+    End,
+}
+
+fn visible_width(s: &AnsiString<'_>) -> isize {
+    s.as_str().chars().count() as isize
+}
+
+/// Run the scan pass: for each token, work out its "size" -- for a
+/// `String` that's just its visible width; for a `Break` or `Begin` it's
+/// the width from that token up to (and including) the matching `End` or
+/// the next sibling `Break`, which can only be known once that token is
+/// reached. A stack of not-yet-sized indices plays the role of the ring
+/// buffer: each `Break`/`End` closes out whatever is on top of the stack.
+fn scan_sizes(tokens: &[Token<'_>]) -> Vec<isize> {
+    let mut sizes = vec![0isize; tokens.len()];
+    let mut pending: Vec<usize> = Vec::new();
+    let mut right_total: isize = 0;
+
+    for (i, tok) in tokens.iter().enumerate() {
+        match tok {
+            Token::Begin(_) => {
+                sizes[i] = -right_total;
+                pending.push(i);
+            }
+            Token::Break(b) => {
+                if let Some(&top) = pending.last() {
+                    if matches!(tokens[top], Token::Break(_)) {
+                        sizes[top] += right_total;
+                        pending.pop();
+                    }
+                }
+                sizes[i] = -right_total;
+                pending.push(i);
+                right_total += b.blank_space as isize;
+            }
+            Token::End => {
+                if let Some(&top) = pending.last() {
+                    if matches!(tokens[top], Token::Break(_)) {
+                        sizes[top] += right_total;
+                        pending.pop();
+                    }
+                }
+                if let Some(top) = pending.pop() {
+                    sizes[top] += right_total;
+                }
+            }
+            Token::String(s) => {
+                let w = visible_width(s);
+                sizes[i] = w;
+                right_total += w;
+            }
+        }
+    }
+
+    // Anything still open never saw a matching End (malformed input); treat
+    // it as too big to fit rather than looping or panicking on it.
+    while let Some(top) = pending.pop() {
+        sizes[top] = INFINITY;
+    }
+
+    sizes
+}
+
+struct Group {
+    kind: Breaks,
+    broken: bool,
+    indent: isize,
+}
+
+/// Run the print pass, wrapping `tokens` to `width` visible columns using
+/// the sizes computed by [`scan_sizes`], and return the result as an owned
+/// collection ready for `Display`.
+pub fn print<'a>(tokens: &[Token<'a>], width: usize) -> AnsiStringVec<'a> {
+    let sizes = scan_sizes(tokens);
+    let mut out = AnsiStringVec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut space: isize = width as isize;
+    // The style of the most recently emitted `String`, applied to the
+    // space run of a non-breaking `Break` so that same-styled words
+    // separated by a fitting break don't pay for a needless reset/prefix
+    // pair in the rendered output.
+    let mut current_style = Style::default();
+
+    for (i, tok) in tokens.iter().enumerate() {
+        match tok {
+            Token::Begin(b) => {
+                let indent = groups.last().map_or(0, |g| g.indent) + b.offset;
+                let broken = sizes[i] > space;
+                groups.push(Group {
+                    kind: b.kind,
+                    broken,
+                    indent,
+                });
+            }
+            Token::End => {
+                groups.pop();
+            }
+            Token::Break(b) => {
+                let should_break = match groups.last() {
+                    Some(Group {
+                        kind: Breaks::Consistent,
+                        broken,
+                        ..
+                    }) => *broken,
+                    // Inconsistent ("fill") groups, and top-level breaks
+                    // outside any group, decide per break.
+                    _ => sizes[i] > space,
+                };
+                if should_break {
+                    let indent = (groups.last().map_or(0, |g| g.indent) + b.indent).max(0) as usize;
+                    out.push(AnsiString::from(Cow::Owned(format!(
+                        "\n{}",
+                        " ".repeat(indent)
+                    ))));
+                    space = width as isize - indent as isize;
+                } else {
+                    out.push(current_style.paint(Cow::Owned(" ".repeat(b.blank_space))));
+                    space -= b.blank_space as isize;
+                }
+            }
+            Token::String(s) => {
+                // An overlong, unbreakable run is emitted as-is: there's no
+                // smaller piece to fall back to, so looping here would
+                // never make it fit.
+                current_style = *s.style_ref();
+                out.push(s.clone());
+                space -= visible_width(s);
+            }
+        }
+    }
+
+    out
+}
+
+/// Word-wrap a sequence of styled runs to `width` visible columns.
+///
+/// Each run is split on spaces into words that fill lines independently
+/// (an `Inconsistent` group), so runs can be wrapped in the middle without
+/// losing their `Style`; words never split. The boundary between two runs
+/// is not a break point, so a word spanning runs stays whole.
+pub fn wrap<'a>(strings: &'a [AnsiString<'a>], width: usize) -> AnsiStringVec<'a> {
+    let mut tokens = vec![Token::Begin(BeginToken {
+        offset: 0,
+        kind: Breaks::Inconsistent,
+    })];
+
+    for run in strings {
+        let style = *run.style_ref();
+        for (j, word) in run.as_str().split(' ').enumerate() {
+            if j > 0 {
+                tokens.push(Token::Break(BreakToken {
+                    blank_space: 1,
+                    indent: 0,
+                }));
+            }
+            tokens.push(Token::String(style.paint(Cow::Borrowed(word))));
+        }
+    }
+
+    tokens.push(Token::End);
+    print(&tokens, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::AnsiStrings;
+    use crate::style::Color::*;
+
+    #[test]
+    fn short_line_does_not_wrap() {
+        let runs = [Red.paint("a short line")];
+        let wrapped = wrap(&runs, 80);
+        assert_eq!(wrapped.to_string(), AnsiStrings(&runs).to_string());
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        let runs = [Style::default().paint("one two three four five")];
+        let wrapped = wrap(&runs, 10);
+        assert_eq!(wrapped.to_string(), "one two\nthree four\nfive");
+    }
+
+    #[test]
+    fn style_is_preserved_across_wrapped_lines() {
+        let runs = [Red.paint("one two three")];
+        let wrapped = wrap(&runs, 5);
+        let lines: Vec<_> = wrapped.to_string().lines().map(|l| l.to_string()).collect();
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            assert!(line.contains("\x1B[31m"), "{:?} missing style prefix", line);
+        }
+    }
+
+    #[test]
+    fn overlong_word_is_emitted_as_is() {
+        let runs = [Style::default().paint("supercalifragilisticexpialidocious")];
+        let wrapped = wrap(&runs, 5);
+        assert_eq!(wrapped.to_string(), "supercalifragilisticexpialidocious");
+    }
+}
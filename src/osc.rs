@@ -0,0 +1,186 @@
+//! A small, standalone API for emitting well-formed OSC (Operating System
+//! Command) escape sequences as plain [`Display`]-able values.
+//!
+//! [`crate::display::AnsiGenericString`] already knows how to attach a
+//! hyperlink, title, clipboard write or notification to a *styled* run and
+//! thread it through [`AnsiGenericStrings`](crate::display::AnsiGenericStrings)'s
+//! minimal-escape-sequence rendering. This module is for the simpler case:
+//! building one of these sequences on its own, with no `Style` involved, to
+//! write straight to a terminal (e.g. `print!("{}", osc::set_title("build done"))`).
+//!
+//! Every sequence here is terminated with `ST` (`ESC \`) by default. Build
+//! with the `osc_bel_terminator` feature to terminate with `BEL` (`\x07`)
+//! instead, for terminals that only recognize that form.
+use std::fmt;
+
+use crate::display::base64_encode;
+
+#[cfg(not(feature = "osc_bel_terminator"))]
+const TERMINATOR: &str = "\x1B\\";
+#[cfg(feature = "osc_bel_terminator")]
+const TERMINATOR: &str = "\x07";
+
+/// Write the `ESC ] <code> ;` prefix shared by every OSC sequence.
+fn write_prefix(f: &mut fmt::Formatter<'_>, code: &str) -> fmt::Result {
+    write!(f, "\x1B]{};", code)
+}
+
+/// OSC 8: a hyperlink wrapping some `Display`-able content, emitted as a
+/// matching open/close pair around it.
+///
+/// Build with [`hyperlink`] and, if several separate runs (e.g. a link
+/// split across wrapped lines) should be treated by the terminal as one
+/// logical link, tag them all with the same [`Hyperlink::with_id`].
+pub struct Hyperlink<'a, D> {
+    url: &'a str,
+    id: Option<&'a str>,
+    content: D,
+}
+
+impl<'a, D> Hyperlink<'a, D> {
+    /// Group this link with any other `Hyperlink` sharing the same `id`,
+    /// so the terminal highlights and activates them together.
+    pub fn with_id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+impl<'a, D: fmt::Display> fmt::Display for Hyperlink<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prefix(f, "8")?;
+        if let Some(id) = self.id {
+            write!(f, "id={}", id)?;
+        }
+        write!(f, ";{}{}", self.url, TERMINATOR)?;
+        write!(f, "{}", self.content)?;
+        write_prefix(f, "8")?;
+        if let Some(id) = self.id {
+            write!(f, "id={}", id)?;
+        }
+        write!(f, ";{}", TERMINATOR)
+    }
+}
+
+/// Build an OSC 8 hyperlink wrapping `content`. Use [`Hyperlink::with_id`]
+/// to group it with other runs of the same logical link.
+pub fn hyperlink<D: fmt::Display>(url: &str, content: D) -> Hyperlink<'_, D> {
+    Hyperlink {
+        url,
+        id: None,
+        content,
+    }
+}
+
+/// Which of a terminal's title fields an OSC title sequence updates.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum TitleKind {
+    /// OSC 0: both the icon and window title.
+    IconAndWindow,
+    /// OSC 1: the icon title only.
+    Icon,
+    /// OSC 2: the window title only.
+    Window,
+}
+
+impl TitleKind {
+    fn code(self) -> &'static str {
+        match self {
+            TitleKind::IconAndWindow => "0",
+            TitleKind::Icon => "1",
+            TitleKind::Window => "2",
+        }
+    }
+}
+
+/// An OSC 0/1/2 window/icon title sequence.
+pub struct Title<'a, D> {
+    kind: TitleKind,
+    text: D,
+    _marker: std::marker::PhantomData<&'a ()>,
+}
+
+impl<'a, D: fmt::Display> fmt::Display for Title<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prefix(f, self.kind.code())?;
+        write!(f, "{}{}", self.text, TERMINATOR)
+    }
+}
+
+/// Set the window title (OSC 2).
+pub fn set_title<D: fmt::Display>(text: D) -> Title<'static, D> {
+    title(TitleKind::Window, text)
+}
+
+/// Set the icon title (OSC 1).
+pub fn set_icon_title<D: fmt::Display>(text: D) -> Title<'static, D> {
+    title(TitleKind::Icon, text)
+}
+
+/// Set the title field selected by `kind`: OSC 0 for both the icon and
+/// window title, OSC 1 for the icon title, OSC 2 for the window title.
+pub fn title<D: fmt::Display>(kind: TitleKind, text: D) -> Title<'static, D> {
+    Title {
+        kind,
+        text,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// An OSC 52 clipboard write: `data` is base64-encoded at display time.
+pub struct Clipboard<D> {
+    data: D,
+}
+
+impl<D: AsRef<[u8]>> fmt::Display for Clipboard<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prefix(f, "52;c")?;
+        write!(f, "{}{}", base64_encode(self.data.as_ref()), TERMINATOR)
+    }
+}
+
+/// Write `data` to the system clipboard (OSC 52), base64-encoding the
+/// payload as required by the spec.
+pub fn clipboard<D: AsRef<[u8]>>(data: D) -> Clipboard<D> {
+    Clipboard { data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyperlink_has_no_id_by_default() {
+        let link = hyperlink("https://example.com", "example").to_string();
+        assert_eq!(
+            link,
+            "\x1B]8;;https://example.com\x1B\\example\x1B]8;;\x1B\\"
+        );
+    }
+
+    #[test]
+    fn hyperlink_with_id_repeats_it_on_both_ends() {
+        let link = hyperlink("https://example.com", "example")
+            .with_id("abc")
+            .to_string();
+        assert_eq!(
+            link,
+            "\x1B]8;id=abc;https://example.com\x1B\\example\x1B]8;id=abc;\x1B\\"
+        );
+    }
+
+    #[test]
+    fn set_title_uses_osc_2() {
+        assert_eq!(set_title("build done").to_string(), "\x1B]2;build done\x1B\\");
+    }
+
+    #[test]
+    fn set_icon_title_uses_osc_1() {
+        assert_eq!(set_icon_title("build").to_string(), "\x1B]1;build\x1B\\");
+    }
+
+    #[test]
+    fn clipboard_base64_encodes_the_payload() {
+        assert_eq!(clipboard("hi").to_string(), "\x1B]52;c;aGk=\x1B\\");
+    }
+}
@@ -0,0 +1,467 @@
+//! ANSI styles and colors: [`Style`] bundles the on/off attributes and
+//! optional foreground/background/underline colors that [`AnsiGenericString`]
+//! (see [`crate::display`]) turns into a pair of `prefix`/`suffix` escape
+//! sequences around a run of text.
+use std::fmt;
+
+/// A foreground, background, or underline color.
+///
+/// `Fixed` selects one of the 256 indexed terminal colors, and `Rgb` asks
+/// for a specific 24-bit color on terminals that support it; both degrade
+/// to the nearest basic color on terminals that don't.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Color {
+    Black,
+    DarkGray,
+    Red,
+    LightRed,
+    Green,
+    LightGreen,
+    Yellow,
+    LightYellow,
+    Blue,
+    LightBlue,
+    Purple,
+    LightPurple,
+    Magenta,
+    LightMagenta,
+    Cyan,
+    LightCyan,
+    White,
+    LightGray,
+    Fixed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The 256-color palette index for one of the sixteen named colors.
+    /// `Fixed`/`Rgb` are handled separately by their callers.
+    fn ansi_256_index(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Purple | Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::DarkGray => 8,
+            Color::LightRed => 9,
+            Color::LightGreen => 10,
+            Color::LightYellow => 11,
+            Color::LightBlue => 12,
+            Color::LightPurple | Color::LightMagenta => 13,
+            Color::LightCyan => 14,
+            Color::LightGray => 15,
+            Color::Fixed(n) => n,
+            Color::Rgb(..) => unreachable!("Rgb colors are encoded directly, not via a palette index"),
+        }
+    }
+
+    /// The `<n>` parameters following a `38;`/`48;`/`58;` introducer.
+    fn ansi_color_code(self) -> String {
+        match self {
+            Color::Rgb(r, g, b) => format!("2;{};{};{}", r, g, b),
+            _ => format!("5;{}", self.ansi_256_index()),
+        }
+    }
+
+    fn ansi_fg_code(self) -> String {
+        match self {
+            Color::Black => "30".into(),
+            Color::Red => "31".into(),
+            Color::Green => "32".into(),
+            Color::Yellow => "33".into(),
+            Color::Blue => "34".into(),
+            Color::Purple | Color::Magenta => "35".into(),
+            Color::Cyan => "36".into(),
+            Color::White => "37".into(),
+            Color::DarkGray => "90".into(),
+            Color::LightRed => "91".into(),
+            Color::LightGreen => "92".into(),
+            Color::LightYellow => "93".into(),
+            Color::LightBlue => "94".into(),
+            Color::LightPurple | Color::LightMagenta => "95".into(),
+            Color::LightCyan => "96".into(),
+            Color::LightGray => "97".into(),
+            Color::Fixed(_) | Color::Rgb(..) => format!("38;{}", self.ansi_color_code()),
+        }
+    }
+
+    fn ansi_bg_code(self) -> String {
+        match self {
+            Color::Black => "40".into(),
+            Color::Red => "41".into(),
+            Color::Green => "42".into(),
+            Color::Yellow => "43".into(),
+            Color::Blue => "44".into(),
+            Color::Purple | Color::Magenta => "45".into(),
+            Color::Cyan => "46".into(),
+            Color::White => "47".into(),
+            Color::DarkGray => "100".into(),
+            Color::LightRed => "101".into(),
+            Color::LightGreen => "102".into(),
+            Color::LightYellow => "103".into(),
+            Color::LightBlue => "104".into(),
+            Color::LightPurple | Color::LightMagenta => "105".into(),
+            Color::LightCyan => "106".into(),
+            Color::LightGray => "107".into(),
+            Color::Fixed(_) | Color::Rgb(..) => format!("48;{}", self.ansi_color_code()),
+        }
+    }
+
+    /// The `58;...` parameters used to color the underline itself rather
+    /// than the foreground text.
+    fn ansi_underline_code(self) -> String {
+        format!("58;{}", self.ansi_color_code())
+    }
+
+    /// A `Style` with no attributes set other than this foreground color.
+    #[must_use]
+    pub fn normal(self) -> Style {
+        Style {
+            foreground: Some(self),
+            ..Style::default()
+        }
+    }
+
+    #[must_use]
+    pub fn bold(self) -> Style {
+        Style {
+            is_bold: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn dimmed(self) -> Style {
+        Style {
+            is_dimmed: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn italic(self) -> Style {
+        Style {
+            is_italic: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn underline(self) -> Style {
+        Style {
+            is_underline: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn blink(self) -> Style {
+        Style {
+            is_blink: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Style {
+        Style {
+            is_reverse: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn hidden(self) -> Style {
+        Style {
+            is_hidden: true,
+            ..self.normal()
+        }
+    }
+
+    #[must_use]
+    pub fn strikethrough(self) -> Style {
+        Style {
+            is_strikethrough: true,
+            ..self.normal()
+        }
+    }
+
+    /// Use this color as the background of a `Style`, keeping its other
+    /// attributes (e.g. `Color::Red.normal().on(Color::Blue)`).
+    #[must_use]
+    pub fn on(self, background: Color) -> Style {
+        Style {
+            background: Some(background),
+            ..self.normal()
+        }
+    }
+}
+
+/// How an underline is drawn. `Single` is the ordinary, unparameterized
+/// underline (SGR `4`); the rest are the colon-parameterized SGR `4:n`
+/// forms that modern terminals (e.g. kitty, iTerm2, VTE-based terminals)
+/// understand.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum UnderlineStyle {
+    #[default]
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// The SGR `4` parameter for this style: plain `"4"` for `Single` (so
+    /// terminals that don't understand the colon forms still get a legacy
+    /// underline), `"4:n"` otherwise.
+    ///
+    /// Built with the `gnu_legacy` feature, `Single` is zero-padded to
+    /// `"04"` to match the codes GNU `ls`/`grep` emit.
+    fn sgr_code(self) -> &'static str {
+        match self {
+            #[cfg(feature = "gnu_legacy")]
+            UnderlineStyle::Single => "04",
+            #[cfg(not(feature = "gnu_legacy"))]
+            UnderlineStyle::Single => "4",
+            UnderlineStyle::Double => "4:2",
+            UnderlineStyle::Curly => "4:3",
+            UnderlineStyle::Dotted => "4:4",
+            UnderlineStyle::Dashed => "4:5",
+        }
+    }
+}
+
+/// A collection of properties that format a string using ANSI escape
+/// codes: attribute flags plus optional foreground, background, and
+/// underline colors.
+///
+/// `break_crate_api` is a reserved field kept for binary compatibility
+/// with existing callers that construct a `Style` with a full field
+/// literal. It emits no SGR code of its own, but a `Style` with it set is
+/// not [`Style::is_plain`], so its `suffix` is a `RESET`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub is_bold: bool,
+    pub is_dimmed: bool,
+    pub is_italic: bool,
+    pub is_underline: bool,
+    pub is_blink: bool,
+    pub is_reverse: bool,
+    pub is_hidden: bool,
+    pub is_strikethrough: bool,
+    /// How the underline is drawn when `is_underline` is set. Defaults to
+    /// `Single`, the plain, unparameterized underline.
+    pub underline_style: UnderlineStyle,
+    /// An underline color distinct from the foreground color, emitted via
+    /// SGR `58`. Only meaningful when `is_underline` is set.
+    pub underline_color: Option<Color>,
+    pub with_reset: bool,
+    pub break_crate_api: bool,
+}
+
+impl Style {
+    /// A `Style` with no properties set.
+    #[must_use]
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Whether this style has no visible effect at all: no colors, no
+    /// attribute flags.
+    #[must_use]
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    #[must_use]
+    pub fn bold(&self) -> Style {
+        Style {
+            is_bold: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn dimmed(&self) -> Style {
+        Style {
+            is_dimmed: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn italic(&self) -> Style {
+        Style {
+            is_italic: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn underline(&self) -> Style {
+        Style {
+            is_underline: true,
+            ..*self
+        }
+    }
+
+    /// Set the style used to draw the underline (implies `is_underline`).
+    #[must_use]
+    pub fn underline_style(&self, style: UnderlineStyle) -> Style {
+        Style {
+            is_underline: true,
+            underline_style: style,
+            ..*self
+        }
+    }
+
+    /// Color the underline independently of the foreground text (implies
+    /// `is_underline`).
+    #[must_use]
+    pub fn underline_color(&self, color: Color) -> Style {
+        Style {
+            is_underline: true,
+            underline_color: Some(color),
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn blink(&self) -> Style {
+        Style {
+            is_blink: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn reverse(&self) -> Style {
+        Style {
+            is_reverse: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn hidden(&self) -> Style {
+        Style {
+            is_hidden: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn strikethrough(&self) -> Style {
+        Style {
+            is_strikethrough: true,
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn fg(&self, foreground: Color) -> Style {
+        Style {
+            foreground: Some(foreground),
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn on(&self, background: Color) -> Style {
+        Style {
+            background: Some(background),
+            ..*self
+        }
+    }
+
+    /// The `\x1B[...m` sequence for just this style's own attributes and
+    /// colors, with no leading reset. Empty if none of them are set.
+    fn attribute_codes(&self) -> String {
+        let mut codes: Vec<String> = Vec::new();
+        if self.is_bold {
+            codes.push("1".into());
+        }
+        if self.is_dimmed {
+            codes.push("2".into());
+        }
+        if self.is_italic {
+            codes.push("3".into());
+        }
+        if self.is_underline {
+            codes.push(self.underline_style.sgr_code().into());
+        }
+        if self.is_blink {
+            codes.push("5".into());
+        }
+        if self.is_reverse {
+            codes.push("7".into());
+        }
+        if self.is_hidden {
+            codes.push("8".into());
+        }
+        if self.is_strikethrough {
+            codes.push("9".into());
+        }
+        if let Some(fg) = self.foreground {
+            codes.push(fg.ansi_fg_code());
+        }
+        if let Some(bg) = self.background {
+            codes.push(bg.ansi_bg_code());
+        }
+        if self.is_underline {
+            if let Some(uc) = self.underline_color {
+                codes.push(uc.ansi_underline_code());
+            }
+        }
+
+        if codes.is_empty() {
+            String::new()
+        } else {
+            format!("\x1B[{}m", codes.join(";"))
+        }
+    }
+
+    /// The `\x1B[...m` sequence that turns on this style's attributes and
+    /// colors, preceded by a `RESET` if `with_reset` is set so the run's
+    /// appearance never depends on whatever style was active before it.
+    #[must_use]
+    pub fn prefix(&self) -> String {
+        if self.with_reset {
+            self.prefix_with_reset()
+        } else {
+            self.attribute_codes()
+        }
+    }
+
+    /// Like [`Style::prefix`], but always leads with a `RESET` regardless
+    /// of `with_reset`.
+    #[must_use]
+    pub fn prefix_with_reset(&self) -> String {
+        format!("{}{}", crate::ansi::RESET, self.attribute_codes())
+    }
+
+    /// The `\x1B[0m` reset sequence that undoes this style's attributes.
+    /// Empty for a plain style, since there's nothing to undo.
+    #[must_use]
+    pub fn suffix(&self) -> &'static str {
+        if self.is_plain() {
+            ""
+        } else {
+            crate::ansi::RESET
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    /// Renders `self.prefix()`, with no content or suffix following it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix())
+    }
+}
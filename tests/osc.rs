@@ -1,11 +1,93 @@
 mod osc {
-    use std::process::{Command, Stdio};
+    use std::process::{Child, Command, ExitStatus, Stdio};
+    use std::time::{Duration, Instant};
 
     trait Terminal {
         fn supported() -> bool;
         fn cmd() -> Command;
     }
 
+    /// Whether `path` names an existing, executable regular file.
+    fn is_executable_file(path: &std::path::Path) -> bool {
+        let Ok(meta) = std::fs::metadata(path) else {
+            return false;
+        };
+        if !meta.is_file() {
+            return false;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            meta.permissions().mode() & 0o111 != 0
+        }
+        #[cfg(not(unix))]
+        {
+            true
+        }
+    }
+
+    /// A small `which`-style resolver: is `cmd` found on `$PATH`? On
+    /// Windows, also tries each `PATHEXT` suffix (`.exe`, `.bat`, ...) in
+    /// addition to the name as given.
+    fn command_on_path(cmd: &str) -> bool {
+        let Some(path_var) = std::env::var_os("PATH") else {
+            return false;
+        };
+
+        for dir in std::env::split_paths(&path_var) {
+            if is_executable_file(&dir.join(cmd)) {
+                return true;
+            }
+
+            #[cfg(windows)]
+            if let Ok(pathext) = std::env::var("PATHEXT") {
+                for ext in pathext.split(';') {
+                    if is_executable_file(&dir.join(format!("{cmd}{ext}"))) {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// How long to wait for a launched terminal to exit before giving up on
+    /// it, overridable via `NU_ANSI_TERM_TERM_TIMEOUT` (seconds).
+    fn term_timeout() -> Duration {
+        std::env::var("NU_ANSI_TERM_TERM_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(15))
+    }
+
+    /// Wait for `child` to exit, polling rather than blocking indefinitely
+    /// so a GUI terminal that never exits (e.g. a detached `wt.exe`, or
+    /// `gnome-terminal --wait` against a terminal server that ignores
+    /// `--wait`) can't hang the test suite. If the deadline passes, the
+    /// child is killed and reaped so no zombie terminal is left behind, and
+    /// the test fails with a clear message.
+    fn wait_with_timeout(mut child: Child, label: &str) -> ExitStatus {
+        let timeout = term_timeout();
+        let start = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait().expect("Failed to poll terminal") {
+                return status;
+            }
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                panic!(
+                    "{} did not exit within {:?} (set NU_ANSI_TERM_TERM_TIMEOUT to adjust)",
+                    label, timeout
+                );
+            }
+            std::thread::sleep(Duration::from_millis(100).min(timeout - elapsed));
+        }
+    }
+
     macro_rules! term {
         ($name:ident, $plat:meta , $cmd:literal, $( $arg:literal),* $(,)?) => {
             struct $name;
@@ -13,7 +95,7 @@ mod osc {
             impl Terminal for $name {
                 fn supported() -> bool {
                     #[cfg($plat)]
-                    { true }
+                    { command_on_path($cmd) }
                     #[cfg(not($plat))]
                     { false }
                 }
@@ -33,38 +115,100 @@ mod osc {
     // TODO Not quite working either
     term!(TerminalExe, target_family = "windows", "wt.exe",);
 
-    // From: https://ss64.com/osx/open.html
-    /* Mac Terminal.app commented out because doesn't quite work
-    term!(
-        TerminalApp,
-        target_os = "macos",
-        "open",
-        "-n",
-        "-F",
-        "-W",
-        "-a",
-        "Terminal.app",
-        // Alternative: "/System/Applications/Utilities/Terminal.app/Contents/MacOS/Terminal",
-        // Alternative: "/Applications/Utilities/Terminal.app/Contents/MacOS/Terminal",
-    );
+    // Terminal.app (and iTerm2) aren't driven by a plain `bash -c` argument
+    // like the other terminals, so they don't fit the `term!` macro: the
+    // command has to be written to a temp script (AppleScript string
+    // literals have no clean way to escape an arbitrary shell command) and
+    // run through `osascript`, which blocks for us until the `do script`
+    // window is closed.
+    //
+    // `target_vendor = "apple"` rather than `target_os = "macos"` so this
+    // also covers Catalyst and other Apple targets that can shell out to
+    // `osascript`.
+    #[cfg(target_vendor = "apple")]
+    struct TerminalApp;
+
+    #[cfg(target_vendor = "apple")]
+    impl TerminalApp {
+        /// Prefer iTerm2 when it's installed; it's almost always the
+        /// frontmost choice on a dev machine that has it, and it honors
+        /// the same `tell application ... do script` AppleScript as
+        /// Terminal.app.
+        fn app_name() -> &'static str {
+            if std::path::Path::new("/Applications/iTerm.app").exists() {
+                "iTerm"
+            } else {
+                "Terminal"
+            }
+        }
+    }
 
-    // Wildly untested AppleScript chunk. I've seen all except the "in a"
-    // parts in various AppleScript examples
-    const APPLESCRIPT: &str = r#"
-        tell application "Terminal"
-            activate
-            do script "/bin/zsh -c {}" in a new Terminal window
-        end tell
-    "#;
+    #[cfg(target_vendor = "apple")]
+    impl Terminal for TerminalApp {
+        fn supported() -> bool {
+            command_on_path("osascript")
+        }
 
-    term!(
-        TerminalApp,
-        target_os = "macos",
-        "osascript",
-        "-e",
-        // TODO pass format!(APPLESCRIPT, which_example) here
-    );
-    */
+        fn cmd() -> Command {
+            Command::new("osascript")
+        }
+    }
+
+    /// Write `shell_command` to a temporary script and drive it through
+    /// `osascript`, returning once the `do script` window has closed.
+    ///
+    /// Writing the command to a file sidesteps AppleScript string
+    /// escaping entirely: the script text embedded in the `-e` argument is
+    /// just a fixed path, never attacker- or example-name-controlled
+    /// shell text.
+    #[cfg(target_vendor = "apple")]
+    fn run_in_terminal_app(shell_command: &str) -> ExitStatus {
+        use std::io::Write as _;
+
+        let mut script_path = std::env::temp_dir();
+        script_path.push(format!("nu-ansi-term-osc-test-{}.sh", std::process::id()));
+
+        {
+            let mut script = std::fs::File::create(&script_path)
+                .expect("Failed to create temporary terminal script");
+            writeln!(script, "#!/bin/sh").unwrap();
+            writeln!(script, "{}", shell_command).unwrap();
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o700))
+                .expect("Failed to make terminal script executable");
+        }
+
+        // `do script` leaves the tab's shell sitting at an interactive
+        // prompt once the command finishes, so the tab never closes on its
+        // own; appending `exit` makes the shell (and so the tab) go away,
+        // and polling `exists win` is how AppleScript waits for that.
+        let applescript = format!(
+            r#"tell application "{app}"
+                activate
+                set win to do script "{script}; exit"
+                repeat
+                    delay 1
+                    if not (exists win) then exit repeat
+                end repeat
+            end tell"#,
+            app = TerminalApp::app_name(),
+            script = script_path.display(),
+        );
+
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(applescript).stdin(Stdio::null());
+
+        let result = cmd
+            .spawn()
+            .map(|child| wait_with_timeout(child, "TerminalApp"));
+
+        let _ = std::fs::remove_file(&script_path);
+
+        result.expect("Failed to launch osascript")
+    }
 
     term!(
         GnomeTerminal,
@@ -136,10 +280,8 @@ mod osc {
 
                     eprintln!("Running {:?} in {}", cmd, stringify!($term));
                     if let Ok(child) = cmd.spawn() {
-                        let output = child
-                            .wait_with_output()
-                            .expect("Failed to wait for terminal");
-                        assert!(output.status.success(), "{:?}", output);
+                        let status = wait_with_timeout(child, stringify!($term));
+                        assert!(status.success(), "{:?}", status);
                     }
                 }
                 // else we expect platform cannot run this
@@ -151,9 +293,33 @@ mod osc {
         ($( $term:ident ),+) => { $( test_one_term!($term); )+ }
     }
 
+    #[cfg(target_vendor = "apple")]
+    macro_rules! test_terminal_app {
+        () => {
+            #[test]
+            #[ignore]
+            #[allow(non_snake_case)]
+            fn TerminalApp() {
+                if TerminalApp::supported() {
+                    let status = run_in_terminal_app(&format!(
+                        "cargo run --example {} -- --sleep 10000",
+                        EXAMPLE
+                    ));
+                    assert!(status.success(), "{:?}", status);
+                }
+            }
+        };
+    }
+
+    #[cfg(not(target_vendor = "apple"))]
+    macro_rules! test_terminal_app {
+        () => {};
+    }
+
     macro_rules! test_all_terms {
         () => {
-            test_terms!(TerminalExe, GnomeTerminal, XTerm, Rxvt, ETerm); //, TerminalApp);
+            test_terms!(TerminalExe, GnomeTerminal, XTerm, Rxvt, ETerm);
+            test_terminal_app!();
         };
     }
 
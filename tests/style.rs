@@ -1,4 +1,4 @@
-use nu_ansi_term::Style;
+use nu_ansi_term::{AnsiStrings, Color, Style, UnderlineStyle};
 
 #[test]
 fn manual_instance_style() {
@@ -22,8 +22,86 @@ fn manual_instance_style() {
         is_reverse: false,
         is_hidden: false,
         is_strikethrough: false,
+        underline_style: UnderlineStyle::Single,
+        underline_color: None,
         with_reset: false,
         break_crate_api: false,
     };
     assert_eq!(Style::default(), s);
 }
+
+#[test]
+fn underline_style_emits_colon_parameterized_sgr() {
+    let style = Style::default().underline_style(UnderlineStyle::Curly);
+    assert_eq!(style.prefix(), "\x1B[4:3m");
+
+    let style = Style::default().underline_style(UnderlineStyle::Dotted);
+    assert_eq!(style.prefix(), "\x1B[4:4m");
+}
+
+#[test]
+fn underline_style_falls_back_to_plain_4_for_single() {
+    let style = Style::default().underline();
+    #[cfg(feature = "gnu_legacy")]
+    assert_eq!(style.prefix(), "\x1B[04m");
+    #[cfg(not(feature = "gnu_legacy"))]
+    assert_eq!(style.prefix(), "\x1B[4m");
+}
+
+#[test]
+fn underline_color_emits_sgr_58() {
+    let style = Style::default().underline_color(Color::Red);
+    #[cfg(feature = "gnu_legacy")]
+    assert_eq!(style.prefix(), "\x1B[04;58;5;1m");
+    #[cfg(not(feature = "gnu_legacy"))]
+    assert_eq!(style.prefix(), "\x1B[4;58;5;1m");
+}
+
+#[test]
+fn with_reset_forces_a_leading_reset() {
+    let style = Style {
+        with_reset: true,
+        ..Style::default()
+    };
+    assert_eq!(style.prefix(), "\x1B[0m");
+
+    let style = Style {
+        is_bold: true,
+        with_reset: true,
+        ..Style::default()
+    };
+    assert_eq!(style.prefix(), "\x1B[0m\x1B[1m");
+    assert_eq!(style.prefix(), style.prefix_with_reset());
+}
+
+#[test]
+fn underline_variants_reset_and_reemit_through_ansi_strings() {
+    // Going from an underlined, colored run to a plain one can't be
+    // expressed as extra SGR params on top of the first -- it has to
+    // close with a real reset so the dropped color and underline don't
+    // bleed into the next run.
+    let curly = Style::default()
+        .underline_style(UnderlineStyle::Curly)
+        .fg(Color::Red)
+        .paint("a");
+    let plain = Style::default().paint("b");
+    let dotted = Style::default().underline_style(UnderlineStyle::Dotted).paint("c");
+
+    let joined = AnsiStrings(&[curly, plain, dotted]).to_string();
+    assert_eq!(joined, "\x1B[4:3;31ma\x1B[0mb\x1B[4:4mc\x1B[0m");
+}
+
+#[test]
+fn underline_variant_changes_without_turning_underline_off() {
+    // Staying underlined the whole time but switching variants should just
+    // re-emit the new colon-parameterized code, not a stale `4` or a
+    // needless reset.
+    let single = Style::default().underline().paint("a");
+    let curly = Style::default().underline_style(UnderlineStyle::Curly).paint("b");
+
+    let joined = AnsiStrings(&[single, curly]).to_string();
+    #[cfg(feature = "gnu_legacy")]
+    assert_eq!(joined, "\x1B[04ma\x1B[4:3mb\x1B[0m");
+    #[cfg(not(feature = "gnu_legacy"))]
+    assert_eq!(joined, "\x1B[4ma\x1B[4:3mb\x1B[0m");
+}